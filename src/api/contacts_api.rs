@@ -1,8 +1,71 @@
+use crate::client_config::{self, ClientConfig};
+use crate::contact::{Contact, GeweResponse, SearchResult};
+use crate::error::GeweError;
+use crate::relationship::{AddOption, FriendRequest, RelationshipState, Scene};
 use crate::user::Wxid;
 use crate::util;
+use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
 use std::error::Error;
 
+/// Posts to a gewe route and decodes the envelope into `T`, turning both
+/// transport failures and non-200 `ret` codes into a [`GeweError`].
+///
+/// This is what the `_typed` wrappers below use instead of calling
+/// [`util::gewe_post_json`] directly, so they get compile-time field access
+/// without duplicating envelope-parsing in every function.
+async fn typed_post<T: DeserializeOwned>(
+    route: &str,
+    params: Option<Value>,
+) -> Result<T, GeweError> {
+    let value = util::gewe_post_json(route, params)
+        .await
+        .map_err(GeweError::Transport)?;
+    let response: GeweResponse<T> = serde_json::from_value(value)?;
+    response.into_data()
+}
+
+/// Posts to a gewe action route and checks only the `ret` code.
+///
+/// Use this instead of [`typed_post`] for routes that return no `data` on
+/// success (`/contacts/search` add/verify, `/contacts/deleteFriend`,
+/// `/contacts/setFriendPermissions`), so a missing `data` field on an
+/// otherwise successful response isn't mistaken for a failure.
+async fn typed_action(route: &str, params: Option<Value>) -> Result<(), GeweError> {
+    let value = util::gewe_post_json(route, params)
+        .await
+        .map_err(GeweError::Transport)?;
+    let response: GeweResponse<Value> = serde_json::from_value(value)?;
+    response.check_ok()
+}
+
+/// Like [`typed_post`], but rate limited and retried per `config`: a
+/// [`ClientConfig`] token bucket/semaphore gates the call, and a non-200
+/// `ret` that [`ClientConfig::is_retryable`] flags is retried with backoff
+/// instead of failing fast.
+async fn typed_post_with_config<T: DeserializeOwned>(
+    route: &str,
+    params: Option<Value>,
+    config: &ClientConfig,
+) -> Result<T, GeweError> {
+    client_config::with_retry(config, route, || async {
+        let params = params.clone();
+        typed_post(route, params).await
+    })
+    .await
+}
+
+/// The wxid buckets returned by `/contacts/fetchContactsList`.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ContactsList {
+    #[serde(default)]
+    pub friends: Vec<Wxid>,
+    #[serde(default)]
+    pub chatrooms: Vec<Wxid>,
+    #[serde(default)]
+    pub ghs: Vec<Wxid>,
+}
+
 /// Fetch contacts list API
 ///
 /// Wrapper of calling `/contacts/fetchContactsList` API of the gewe service.
@@ -38,6 +101,30 @@ pub async fn fetch_contacts_list(app_id: &str) -> Result<Value, Box<dyn Error>>
     util::gewe_post_json("/contacts/fetchContactsList", Some(params)).await
 }
 
+/// Typed variant of [`fetch_contacts_list`].
+///
+/// Returns the wxid buckets directly instead of a raw `Value`, ready to be
+/// hydrated into [`Contact`]s via [`get_brief_list_typed`].
+pub async fn fetch_contacts_list_typed(app_id: &str) -> Result<ContactsList, GeweError> {
+    let params = json!({
+        "appId": app_id,
+    });
+    typed_post("/contacts/fetchContactsList", Some(params)).await
+}
+
+/// Like [`fetch_contacts_list_typed`], but rate limited and retried per
+/// `config`. Use this for the full-list fetch, since it's the slowest
+/// `/contacts/*` route and the first one worth throttling.
+pub async fn fetch_contacts_list_typed_with_config(
+    app_id: &str,
+    config: &ClientConfig,
+) -> Result<ContactsList, GeweError> {
+    let params = json!({
+        "appId": app_id,
+    });
+    typed_post_with_config("/contacts/fetchContactsList", Some(params), config).await
+}
+
 /// Fetch cached contacts list API
 ///
 /// Wrapper of calling `/contacts/fetchContactsListCache` API of the gewe service.
@@ -70,6 +157,19 @@ pub async fn fetch_contacts_list_cache(app_id: &str) -> Result<Value, Box<dyn Er
     util::gewe_post_json("/contacts/fetchContactsListCache", Some(params)).await
 }
 
+/// Typed variant of [`fetch_contacts_list_cache`].
+///
+/// Prefer this over [`fetch_contacts_list_typed`] when the 10-minute-old
+/// cache is acceptable — e.g. [`crate::contact_store::ContactStore::refresh`]
+/// uses it so refreshing the local cache doesn't itself trigger the slow,
+/// uncached full fetch.
+pub async fn fetch_contacts_list_cache_typed(app_id: &str) -> Result<ContactsList, GeweError> {
+    let params = json!({
+        "appId": app_id,
+    });
+    typed_post("/contacts/fetchContactsListCache", Some(params)).await
+}
+
 /// Search friend API
 ///
 /// Wrapper of calling `/contacts/search` API of the gewe service.
@@ -105,10 +205,25 @@ pub async fn search_friend(app_id: &str, keyword: &str) -> Result<Value, Box<dyn
     util::gewe_post_json("/contacts/search", Some(params)).await
 }
 
-/// Add friend API
+/// Typed variant of [`search_friend`].
+///
+/// Returns the `v3`/`v4`/`scene` fields needed to drive [`add_friend`]
+/// without the caller hand-walking the raw envelope.
+pub async fn search_friend_typed(app_id: &str, keyword: &str) -> Result<SearchResult, GeweError> {
+    let params = json!({
+        "appId": app_id,
+        "contactsInfo": keyword,
+    });
+    typed_post("/contacts/search", Some(params)).await
+}
+
+/// Add friend API (raw)
 ///
 /// Wrapper of calling `/contacts/search` API of the gewe service.
-/// TODO
+/// Sends or verifies a friend request using raw scene/option integers.
+///
+/// Prefer [`add_friend`] or [`accept_friend`], which take a [`Scene`] and
+/// [`AddOption`] instead of magic numbers.
 ///
 /// # Route
 ///
@@ -116,13 +231,12 @@ pub async fn search_friend(app_id: &str, keyword: &str) -> Result<Value, Box<dyn
 ///
 /// # Parameters
 ///
-/// TODO
 /// - `app_id` - The application identifier associated with the user.
-///
-/// # Examples
-///
-/// TODO
-///
+/// - `scene` - The gewe scene code the request originated from (see [`Scene`]).
+/// - `option` - Add-vs-verify option (see [`AddOption`]).
+/// - `v3` - Opaque token identifying the target, from [`search_friend`].
+/// - `v4` - Opaque ticket accompanying `v3`, from [`search_friend`].
+/// - `content` - Greeting message sent with the request.
 pub async fn search_add(
     app_id: &str,
     scene: i32,
@@ -142,6 +256,72 @@ pub async fn search_add(
     util::gewe_post_json("/contacts/search", Some(params)).await
 }
 
+/// Sends a friend request, or adds the contact outright if `option` is
+/// [`AddOption::Add`].
+///
+/// Typed counterpart of [`search_add`]: takes the `v3`/`v4`/scene values
+/// returned by [`search_friend_typed`] instead of raw integers.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// #[tokio::main]
+/// async fn main() {
+///     use rgewe_api::api::add_friend;
+///     use rgewe_api::relationship::{AddOption, FriendRequest, Scene};
+///
+///     let app_id = "your_app_id";
+///     let request = FriendRequest::new("v3_token", "v4_token", Scene::WeChatId, "Hi, let's connect");
+///     let state = add_friend(app_id, &request, AddOption::Verify).await.unwrap();
+///     println!("{state:?}");
+/// }
+/// ```
+pub async fn add_friend(
+    app_id: &str,
+    request: &FriendRequest,
+    option: AddOption,
+) -> Result<RelationshipState, GeweError> {
+    let params = json!({
+        "appId": app_id,
+        "scene": request.scene.code(),
+        "option": option as i32,
+        "v3": request.v3,
+        "v4": request.v4,
+        "content": request.content,
+    });
+    typed_action("/contacts/search", Some(params)).await?;
+    Ok(match option {
+        AddOption::Add => RelationshipState::Friend,
+        AddOption::Verify => RelationshipState::RequestSent,
+    })
+}
+
+/// Accepts an incoming friend request, wrapping the gewe verify endpoint.
+///
+/// Sends the same request as [`add_friend`] with [`AddOption::Verify`], but
+/// with an empty greeting, and returns [`RelationshipState::Friend`] instead
+/// of [`RelationshipState::RequestSent`]. It is named for the accept side of
+/// the flow: `v3`/`v4`/`scene` here come from a
+/// [`crate::events::ContactEvent::FriendRequestReceived`] rather than a
+/// local search.
+pub async fn accept_friend(
+    app_id: &str,
+    v3: &str,
+    v4: &str,
+    scene: Scene,
+) -> Result<RelationshipState, GeweError> {
+    let params = json!({
+        "appId": app_id,
+        "scene": scene.code(),
+        "option": AddOption::Verify as i32,
+        "v3": v3,
+        "v4": v4,
+        "content": "",
+    });
+    typed_action("/contacts/search", Some(params)).await?;
+    Ok(RelationshipState::Friend)
+}
+
 /// Delete friend API
 ///
 /// Wrapper of calling `/contacts/deleteFriend` API of the gewe service.
@@ -178,6 +358,19 @@ pub async fn delete_friend(app_id: &str, wxid: &Wxid) -> Result<Value, Box<dyn E
     util::gewe_post_json("/contacts/deleteFriend", Some(params)).await
 }
 
+/// Typed variant of [`delete_friend`].
+///
+/// Returns [`RelationshipState::Deleted`] on success, folding the deletion
+/// into the same relationship model [`add_friend`] and [`accept_friend`] use.
+pub async fn delete_friend_typed(app_id: &str, wxid: &Wxid) -> Result<RelationshipState, GeweError> {
+    let params = json!({
+        "appId": app_id,
+        "wxid": wxid,
+    });
+    typed_action("/contacts/deleteFriend", Some(params)).await?;
+    Ok(RelationshipState::Deleted)
+}
+
 #[derive(Debug)]
 #[repr(u32)]
 pub enum ContactOperationType {
@@ -272,6 +465,28 @@ pub async fn set_friend_only_chat(
     util::gewe_post_json("/contacts/setFriendPermissions", Some(params)).await
 }
 
+/// Typed variant of [`set_friend_only_chat`].
+///
+/// Returns [`RelationshipState::ChatOnly`] when `only_chat` is enabled, or
+/// [`RelationshipState::Friend`] when it is lifted.
+pub async fn set_friend_only_chat_typed(
+    app_id: &str,
+    wxid: &Wxid,
+    only_chat: bool,
+) -> Result<RelationshipState, GeweError> {
+    let params = json!({
+        "appId": app_id,
+        "wxid": wxid,
+        "onlyChat": only_chat,
+    });
+    typed_action("/contacts/setFriendPermissions", Some(params)).await?;
+    Ok(if only_chat {
+        RelationshipState::ChatOnly
+    } else {
+        RelationshipState::Friend
+    })
+}
+
 pub async fn set_friend_remark(
     app_id: &str,
     wxid: &Wxid,
@@ -325,6 +540,19 @@ pub async fn get_brief_single(app_id: &str, wxid: &Wxid) -> Result<Value, Box<dy
     util::gewe_post_json("/contacts/getBriefInfo", Some(params)).await
 }
 
+/// Typed variant of [`get_brief_single`].
+pub async fn get_brief_single_typed(app_id: &str, wxid: &Wxid) -> Result<Contact, GeweError> {
+    let params = json!({
+        "appId": app_id,
+        "wxids": vec![wxid],
+    });
+    let mut contacts: Vec<Contact> = typed_post("/contacts/getBriefInfo", Some(params)).await?;
+    contacts.pop().ok_or_else(|| GeweError::Api {
+        ret: 200,
+        msg: format!("no contact returned for wxid {wxid:?}"),
+    })
+}
+
 /// Get brief information for multiple contacts API
 ///
 /// Wrapper of calling `/contacts/getBriefInfo` API of the gewe service.
@@ -362,4 +590,215 @@ pub async fn get_brief_list(app_id: &str, wxids: Vec<Wxid>) -> Result<Value, Box
         "wxids": wxids,
     });
     util::gewe_post_json("/contacts/getBriefInfo", Some(params)).await
+}
+
+/// Default number of wxids sent per `/contacts/getBriefInfo` request.
+///
+/// The gewe endpoint caps how many wxids it accepts per call; forwarding an
+/// arbitrarily large batch in one POST silently truncates it.
+pub const DEFAULT_BRIEF_CHUNK_SIZE: usize = 100;
+
+/// Default number of chunk requests kept in flight at once.
+pub const DEFAULT_BRIEF_CONCURRENCY: usize = 4;
+
+/// Outcome of resolving a (possibly very large) batch of wxids through
+/// chunked, concurrent calls to `/contacts/getBriefInfo`.
+///
+/// `contacts` preserves the input order across chunk boundaries; `failed`
+/// holds the wxids of any chunk whose request failed, paired with the
+/// error, so one bad chunk doesn't discard everything else.
+#[derive(Debug)]
+pub struct BriefListResult {
+    pub contacts: Vec<Contact>,
+    pub failed: Vec<(Vec<Wxid>, GeweError)>,
+}
+
+/// Typed variant of [`get_brief_list`].
+///
+/// Splits `wxids` into chunks of [`DEFAULT_BRIEF_CHUNK_SIZE`] and resolves
+/// them with up to [`DEFAULT_BRIEF_CONCURRENCY`] requests in flight at once.
+/// See [`get_brief_list_typed_with`] to override either default.
+pub async fn get_brief_list_typed(app_id: &str, wxids: Vec<Wxid>) -> Result<BriefListResult, GeweError> {
+    get_brief_list_typed_with(
+        app_id,
+        wxids,
+        DEFAULT_BRIEF_CHUNK_SIZE,
+        DEFAULT_BRIEF_CONCURRENCY,
+    )
+    .await
+}
+
+/// Like [`get_brief_list_typed`], but with an explicit chunk size and
+/// max-concurrency.
+pub async fn get_brief_list_typed_with(
+    app_id: &str,
+    wxids: Vec<Wxid>,
+    chunk_size: usize,
+    concurrency: usize,
+) -> Result<BriefListResult, GeweError> {
+    get_brief_list_chunked(app_id, wxids, chunk_size, concurrency, None).await
+}
+
+/// Like [`get_brief_list_typed_with`], but rate limited and retried per
+/// `config`: each chunk's request goes through the same token
+/// bucket/semaphore/backoff as [`fetch_contacts_list_typed_with_config`], so
+/// a large fan-out can't overrun the service's limits.
+pub async fn get_brief_list_typed_with_config(
+    app_id: &str,
+    wxids: Vec<Wxid>,
+    chunk_size: usize,
+    config: &ClientConfig,
+) -> Result<BriefListResult, GeweError> {
+    get_brief_list_chunked(app_id, wxids, chunk_size, config.max_concurrency, Some(config)).await
+}
+
+/// Runs `make_request` for each chunk with up to `concurrency` requests in
+/// flight at once, then merges the completions back into input order and
+/// isolates any chunk's failure from the rest.
+///
+/// Pulled out of [`get_brief_list_chunked`] as its own generic function so
+/// the concurrency/ordering/partial-failure logic can be exercised directly
+/// with fake, out-of-order-completing futures instead of real network calls.
+async fn merge_chunked_requests<F, Fut>(
+    chunks: Vec<Vec<Wxid>>,
+    concurrency: usize,
+    make_request: F,
+) -> BriefListResult
+where
+    F: Fn(usize, Vec<Wxid>) -> Fut,
+    Fut: std::future::Future<Output = (usize, Vec<Wxid>, Result<Vec<Contact>, GeweError>)>,
+{
+    use futures::stream::{self, StreamExt};
+
+    let mut indexed_results: Vec<(usize, Vec<Wxid>, Result<Vec<Contact>, GeweError>)> =
+        stream::iter(
+            chunks
+                .into_iter()
+                .enumerate()
+                .map(|(index, chunk)| make_request(index, chunk)),
+        )
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    indexed_results.sort_by_key(|(index, _, _)| *index);
+
+    let mut contacts = Vec::new();
+    let mut failed = Vec::new();
+    for (_, chunk, result) in indexed_results {
+        match result {
+            Ok(mut chunk_contacts) => contacts.append(&mut chunk_contacts),
+            Err(err) => failed.push((chunk, err)),
+        }
+    }
+
+    BriefListResult { contacts, failed }
+}
+
+async fn get_brief_list_chunked(
+    app_id: &str,
+    wxids: Vec<Wxid>,
+    chunk_size: usize,
+    concurrency: usize,
+    config: Option<&ClientConfig>,
+) -> Result<BriefListResult, GeweError> {
+    let chunk_size = chunk_size.max(1);
+    let chunks: Vec<Vec<Wxid>> = wxids
+        .chunks(chunk_size)
+        .map(|chunk| chunk.to_vec())
+        .collect();
+
+    let result = merge_chunked_requests(chunks, concurrency, |index, chunk| async move {
+        let params = json!({
+            "appId": app_id,
+            "wxids": chunk,
+        });
+        let result = match config {
+            Some(config) => {
+                typed_post_with_config("/contacts/getBriefInfo", Some(params), config).await
+            }
+            None => typed_post("/contacts/getBriefInfo", Some(params)).await,
+        };
+        (index, chunk, result)
+    })
+    .await;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn contact(wxid: &str) -> Contact {
+        Contact {
+            wxid: wxid.to_string(),
+            nick_name: String::new(),
+            remark: String::new(),
+            alias: String::new(),
+            avatar_url: String::new(),
+            verify_flag: 0,
+        }
+    }
+
+    fn chunk(wxid: &str) -> Vec<Wxid> {
+        vec![Wxid::try_from(wxid).unwrap()]
+    }
+
+    #[tokio::test]
+    async fn merges_out_of_order_completions_in_input_order() {
+        let chunks = vec![chunk("wxid_a"), chunk("wxid_b"), chunk("wxid_c")];
+
+        // Chunk 0 is the slowest to complete, chunk 2 the fastest, so a
+        // correct merge has to reorder by input index, not completion order.
+        let result = merge_chunked_requests(chunks, 3, |index, chunk| async move {
+            let delay_ms = match index {
+                0 => 30,
+                1 => 15,
+                _ => 1,
+            };
+            tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            let wxid = match index {
+                0 => "wxid_a",
+                1 => "wxid_b",
+                _ => "wxid_c",
+            };
+            (index, chunk, Ok(vec![contact(wxid)]))
+        })
+        .await;
+
+        let wxids: Vec<&str> = result.contacts.iter().map(|c| c.wxid.as_str()).collect();
+        assert_eq!(wxids, vec!["wxid_a", "wxid_b", "wxid_c"]);
+        assert!(result.failed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn one_failed_chunk_does_not_discard_the_rest() {
+        let chunks = vec![chunk("wxid_a"), chunk("wxid_b"), chunk("wxid_c")];
+
+        let result = merge_chunked_requests(chunks, 3, |index, chunk| async move {
+            if index == 1 {
+                return (
+                    index,
+                    chunk,
+                    Err(GeweError::Api {
+                        ret: 500,
+                        msg: "boom".to_string(),
+                    }),
+                );
+            }
+            let wxid = if index == 0 { "wxid_a" } else { "wxid_c" };
+            (index, chunk, Ok(vec![contact(wxid)]))
+        })
+        .await;
+
+        let wxids: Vec<&str> = result.contacts.iter().map(|c| c.wxid.as_str()).collect();
+        assert_eq!(wxids, vec!["wxid_a", "wxid_c"]);
+        assert_eq!(result.failed.len(), 1);
+        assert!(matches!(
+            result.failed[0].1,
+            GeweError::Api { ret: 500, .. }
+        ));
+    }
 }
\ No newline at end of file
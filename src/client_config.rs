@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Semaphore;
+
+use crate::error::GeweError;
+
+/// Tunables for rate limiting and retrying calls to the slow `/contacts/*`
+/// routes (`fetch_contacts_list`, the `getBriefInfo` fan-out, etc.).
+///
+/// A `ClientConfig` owns a token bucket per route plus a global
+/// max-concurrency semaphore, and is threaded through
+/// [`crate::util::gewe_post_json`] callers via
+/// [`crate::api::contacts_api::typed_post_with_config`] so heavy operations
+/// stay within the service's limits automatically.
+pub struct ClientConfig {
+    /// Requests allowed per second, per route.
+    pub requests_per_second: f64,
+    /// Maximum requests in flight across all routes at once.
+    pub max_concurrency: usize,
+    /// Maximum attempts (including the first) before giving up.
+    pub max_attempts: u32,
+    /// Base delay for exponential backoff; doubles each retry and is jittered.
+    pub base_backoff: Duration,
+
+    semaphore: Semaphore,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+    /// Monotonic per-call counter mixed into the jitter seed so concurrent
+    /// callers backing off at the same attempt number don't all compute the
+    /// same delay (see [`backoff_for`](Self::backoff_for)).
+    call_seq: AtomicU64,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self::new(5.0, 4, 3, Duration::from_millis(200))
+    }
+}
+
+impl ClientConfig {
+    pub fn new(
+        requests_per_second: f64,
+        max_concurrency: usize,
+        max_attempts: u32,
+        base_backoff: Duration,
+    ) -> Self {
+        Self {
+            requests_per_second,
+            max_concurrency: max_concurrency.max(1),
+            max_attempts: max_attempts.max(1),
+            base_backoff,
+            semaphore: Semaphore::new(max_concurrency.max(1)),
+            buckets: Mutex::new(HashMap::new()),
+            call_seq: AtomicU64::new(0),
+        }
+    }
+
+    /// Blocks until both the global concurrency semaphore and the
+    /// per-route token bucket admit another request to `route`.
+    pub async fn acquire(&self, route: &str) -> tokio::sync::SemaphorePermit<'_> {
+        let permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("ClientConfig semaphore is never closed");
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                buckets
+                    .entry(route.to_string())
+                    .or_insert_with(|| TokenBucket::new(self.requests_per_second))
+                    .try_take()
+            };
+            match wait {
+                None => break,
+                Some(delay) => tokio::time::sleep(delay).await,
+            }
+        }
+        permit
+    }
+
+    /// Whether a gewe `ret` code indicates a transient failure worth
+    /// retrying (rate-limited or otherwise temporary), as opposed to one
+    /// that should fail fast.
+    pub fn is_retryable(ret: i32) -> bool {
+        matches!(ret, 429 | 500 | 503 | 600)
+    }
+
+    /// Backoff delay before retry attempt `attempt` (1-based) on `route`,
+    /// with jitter.
+    ///
+    /// The jitter seed mixes in a counter that advances on every call, so
+    /// two concurrent callers retrying the same route at the same `attempt`
+    /// (e.g. sibling chunks in [`crate::api::contacts_api::get_brief_list_typed_with_config`])
+    /// still land on different delays instead of retrying in lockstep.
+    pub fn backoff_for(&self, route: &str, attempt: u32) -> Duration {
+        let exp = self.base_backoff.as_millis().saturating_mul(1u128 << attempt.min(16));
+        let seq = self.call_seq.fetch_add(1, Ordering::Relaxed);
+        let seed = route
+            .bytes()
+            .fold(seq ^ attempt as u64, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u64));
+        let jitter = (exp as f64 * 0.2 * pseudo_jitter(seed)) as u128;
+        Duration::from_millis((exp + jitter).min(30_000) as u64)
+    }
+}
+
+/// Runs `call` under `config`'s rate limit, retrying on
+/// [`GeweError::Api`] codes [`ClientConfig::is_retryable`] flags, with
+/// exponential backoff and jitter, up to `config.max_attempts`.
+///
+/// `call` is invoked once per attempt since the underlying request can't be
+/// replayed after being consumed.
+pub async fn with_retry<T, F, Fut>(
+    config: &ClientConfig,
+    route: &str,
+    mut call: F,
+) -> Result<T, GeweError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, GeweError>>,
+{
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let _permit = config.acquire(route).await;
+        let result = call().await;
+        drop(_permit);
+
+        match result {
+            Ok(value) => return Ok(value),
+            Err(GeweError::Api { ret, msg }) if ClientConfig::is_retryable(ret) && attempt < config.max_attempts => {
+                tokio::time::sleep(config.backoff_for(route, attempt)).await;
+                let _ = &msg;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// A simple per-route token bucket: one token refills every `1 /
+/// requests_per_second` seconds, up to a burst of one second's worth.
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(refill_per_sec: f64) -> Self {
+        let capacity = refill_per_sec.max(1.0);
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_per_sec,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Takes a token if one is available, or returns how long to wait for
+    /// the next refill.
+    fn try_take(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            let deficit = 1.0 - self.tokens;
+            Some(Duration::from_secs_f64(deficit / self.refill_per_sec.max(0.001)))
+        }
+    }
+}
+
+/// Deterministic pseudo-jitter in `[0, 1)` derived from `seed`, avoiding a
+/// dependency on a random number generator for a small backoff nudge.
+///
+/// Callers must vary `seed` per call (route, attempt, and a monotonic
+/// counter, as [`ClientConfig::backoff_for`] does) — a seed that repeats
+/// across concurrent callers produces identical jitter and defeats the
+/// point of jittering at all.
+fn pseudo_jitter(seed: u64) -> f64 {
+    let n = seed.wrapping_mul(2654435761);
+    (n % 1000) as f64 / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket_allows_burst_then_throttles() {
+        let mut bucket = TokenBucket::new(2.0);
+        assert!(bucket.try_take().is_none(), "first token should be free");
+        assert!(bucket.try_take().is_none(), "burst capacity covers a second token");
+        assert!(
+            bucket.try_take().is_some(),
+            "third immediate token should be throttled"
+        );
+    }
+
+    #[test]
+    fn backoff_for_grows_with_attempt() {
+        let config = ClientConfig::new(5.0, 4, 5, Duration::from_millis(100));
+        let first = config.backoff_for("/contacts/getBriefInfo", 1);
+        let second = config.backoff_for("/contacts/getBriefInfo", 2);
+        assert!(second >= first, "later attempts should back off at least as long");
+    }
+
+    #[test]
+    fn backoff_for_jitter_differs_across_concurrent_calls() {
+        let config = ClientConfig::new(5.0, 4, 5, Duration::from_millis(100));
+        let a = config.backoff_for("/contacts/getBriefInfo", 1);
+        let b = config.backoff_for("/contacts/getBriefInfo", 1);
+        assert_ne!(a, b, "two concurrent backoffs at the same attempt must not match");
+    }
+
+    #[test]
+    fn pseudo_jitter_is_in_unit_range() {
+        for seed in [0, 1, 42, u64::MAX] {
+            let value = pseudo_jitter(seed);
+            assert!((0.0..1.0).contains(&value));
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use serde::Deserialize;
+
+use crate::error::GeweError;
+
+/// Generic envelope returned by every gewe endpoint: a `ret` code, a human
+/// readable `msg`, and the endpoint-specific `data` payload.
+///
+/// Raw wrappers destructure this by hand via `serde_json::Value`; typed
+/// wrappers deserialize straight into `GeweResponse<T>` and call
+/// [`GeweResponse::into_data`] to turn a non-200 `ret` into a [`GeweError`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct GeweResponse<T> {
+    pub ret: i32,
+    pub msg: String,
+    pub data: Option<T>,
+}
+
+impl<T> GeweResponse<T> {
+    /// Unwraps a successful envelope, or turns a failing `ret`/missing `data`
+    /// into a [`GeweError::Api`].
+    ///
+    /// Only use this for routes that actually return a `data` payload on
+    /// success; many action routes (`/contacts/search`,
+    /// `/contacts/deleteFriend`, `/contacts/setFriendPermissions`) return
+    /// `ret: 200` with no `data` at all, and should go through
+    /// [`check_ok`](Self::check_ok) instead.
+    pub fn into_data(self) -> Result<T, GeweError> {
+        if self.ret != 200 {
+            return Err(GeweError::Api {
+                ret: self.ret,
+                msg: self.msg,
+            });
+        }
+        self.data.ok_or_else(|| GeweError::Api {
+            ret: self.ret,
+            msg: "missing data in successful response".to_string(),
+        })
+    }
+
+    /// Checks only the `ret` code, ignoring `data` entirely.
+    ///
+    /// For action routes that signal success via `ret: 200` alone and never
+    /// populate `data`.
+    pub fn check_ok(&self) -> Result<(), GeweError> {
+        if self.ret != 200 {
+            return Err(GeweError::Api {
+                ret: self.ret,
+                msg: self.msg.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A single contact as returned by `/contacts/getBriefInfo`.
+///
+/// Mirrors the subset of fields clients actually need day to day: identity
+/// (`wxid`), display fields (`nick_name`, `remark`, `alias`), the avatar, and
+/// the verify flag gewe uses to distinguish a confirmed friend from someone
+/// who is chat-only.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Contact {
+    pub wxid: String,
+    #[serde(rename = "nickName", default)]
+    pub nick_name: String,
+    #[serde(default)]
+    pub remark: String,
+    #[serde(default)]
+    pub alias: String,
+    #[serde(rename = "bigHeadImgUrl", default)]
+    pub avatar_url: String,
+    #[serde(rename = "verifyFlag", default)]
+    pub verify_flag: i32,
+}
+
+/// Result of a single `/contacts/search` lookup: enough to drive
+/// [`crate::api::add_friend`] without the caller re-parsing raw JSON.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchResult {
+    pub wxid: String,
+    #[serde(rename = "nickName", default)]
+    pub nick_name: String,
+    pub v3: String,
+    pub v4: String,
+    pub scene: i32,
+}
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::api::contacts_api;
+use crate::contact::Contact;
+use crate::error::GeweError;
+use crate::user::Wxid;
+
+/// In-memory cache of the authenticated user's contacts.
+///
+/// `fetch_contacts_list` is documented as time-consuming, so `ContactStore`
+/// pulls wxids through the cached [`contacts_api::fetch_contacts_list_cache_typed`]
+/// instead, hydrates them into [`Contact`]s via `getBriefInfo`, and serves
+/// [`search`](ContactStore::search) locally instead of hitting the network
+/// on every keystroke. Use [`refresh_if_stale`](ContactStore::refresh_if_stale)
+/// to transparently re-fetch once a caller-chosen TTL has elapsed (the gewe
+/// cache itself only lasts 10 minutes, so don't pick a longer one); fall back
+/// to [`contacts_api::search_friend`] for contacts that aren't in the store.
+pub struct ContactStore {
+    app_id: String,
+    contacts: HashMap<String, Contact>,
+    last_refresh: Option<Instant>,
+}
+
+impl ContactStore {
+    pub fn new(app_id: impl Into<String>) -> Self {
+        Self {
+            app_id: app_id.into(),
+            contacts: HashMap::new(),
+            last_refresh: None,
+        }
+    }
+
+    /// Re-pulls the full contact list and rebuilds the local cache.
+    ///
+    /// Returns the wxid chunks that failed to resolve (with their errors),
+    /// if any — those contacts are left out of the cache rather than
+    /// silently missing. Successfully resolved chunks still populate the
+    /// cache and `last_refresh` is still stamped, since a partial refresh is
+    /// more useful than none.
+    pub async fn refresh(&mut self) -> Result<Vec<(Vec<Wxid>, GeweError)>, GeweError> {
+        let list = contacts_api::fetch_contacts_list_cache_typed(&self.app_id).await?;
+        let result = contacts_api::get_brief_list_typed(&self.app_id, list.friends).await?;
+        self.contacts = result
+            .contacts
+            .into_iter()
+            .map(|c| (c.wxid.clone(), c))
+            .collect();
+        self.last_refresh = Some(Instant::now());
+        Ok(result.failed)
+    }
+
+    /// Calls [`refresh`](Self::refresh) only if the cache is empty or older
+    /// than `ttl`, returning `None` if it was still fresh or the refresh's
+    /// failed chunks (if any) otherwise.
+    pub async fn refresh_if_stale(
+        &mut self,
+        ttl: Duration,
+    ) -> Result<Option<Vec<(Vec<Wxid>, GeweError)>>, GeweError> {
+        let stale = match self.last_refresh {
+            Some(at) => at.elapsed() >= ttl,
+            None => true,
+        };
+        if stale {
+            Ok(Some(self.refresh().await?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Looks up a cached contact by wxid without touching the network.
+    pub fn get(&self, wxid: &str) -> Option<&Contact> {
+        self.contacts.get(wxid)
+    }
+
+    /// Ranks cached contacts against `query` using a subsequence match over
+    /// nickname, remark, and alias, and returns them sorted by descending
+    /// score. A contact only matches if every character of `query` appears,
+    /// in order, in at least one of those fields.
+    pub fn search(&self, query: &str) -> Vec<&Contact> {
+        let mut scored: Vec<(f64, &Contact)> = self
+            .contacts
+            .values()
+            .filter_map(|contact| {
+                [&contact.nick_name, &contact.remark, &contact.alias]
+                    .into_iter()
+                    .filter_map(|field| subsequence_score(query, field))
+                    .fold(None, |best: Option<f64>, score| {
+                        Some(best.map_or(score, |b| b.max(score)))
+                    })
+                    .map(|score| (score, contact))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        scored.into_iter().map(|(_, contact)| contact).collect()
+    }
+}
+
+/// Scores `candidate` against `query` as a case-insensitive subsequence
+/// match, or returns `None` if `query` isn't a subsequence of `candidate`.
+///
+/// The score has two independently normalized parts: a length component (one
+/// point per matched character, normalized by candidate length, so a tighter
+/// overall candidate ranks higher) and a placement component (a bonus for
+/// extending a consecutive run and another for landing on a word boundary —
+/// string start, after a space, or a capital following any non-capital —
+/// normalized by query length instead of candidate length). Keeping the
+/// placement bonus independent of candidate length means a true word-boundary
+/// match still outranks a mid-word match even against a much longer
+/// candidate, where dividing everything by candidate length would otherwise
+/// swamp the bonus.
+fn subsequence_score(query: &str, candidate: &str) -> Option<f64> {
+    if query.is_empty() || candidate.is_empty() {
+        return None;
+    }
+    let query_chars: Vec<char> = query.chars().collect();
+    let cand_chars: Vec<char> = candidate.chars().collect();
+
+    let mut qi = 0;
+    let mut run_len = 0u32;
+    let mut last_match: Option<usize> = None;
+    let mut matched = 0u32;
+    let mut placement_bonus = 0.0;
+
+    for (ci, &c) in cand_chars.iter().enumerate() {
+        if qi >= query_chars.len() {
+            break;
+        }
+        if !chars_eq_ignore_case(c, query_chars[qi]) {
+            continue;
+        }
+        let consecutive = last_match == Some(ci.wrapping_sub(1));
+        run_len = if consecutive { run_len + 1 } else { 1 };
+        let at_boundary = ci == 0
+            || cand_chars[ci - 1] == ' '
+            || (c.is_uppercase() && !cand_chars[ci - 1].is_uppercase());
+        placement_bonus += (run_len as f64 - 1.0) * 0.5 + if at_boundary { 1.0 } else { 0.0 };
+        matched += 1;
+        last_match = Some(ci);
+        qi += 1;
+    }
+
+    if qi == query_chars.len() {
+        let length_score = matched as f64 / cand_chars.len() as f64;
+        let placement_score = placement_bonus / query_chars.len() as f64;
+        Some(length_score + placement_score)
+    } else {
+        None
+    }
+}
+
+fn chars_eq_ignore_case(a: char, b: char) -> bool {
+    a.to_lowercase().eq(b.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_subsequence_does_not_match() {
+        assert_eq!(subsequence_score("xyz", "Alice"), None);
+    }
+
+    #[test]
+    fn empty_query_or_candidate_does_not_match() {
+        assert_eq!(subsequence_score("", "Alice"), None);
+        assert_eq!(subsequence_score("al", ""), None);
+    }
+
+    #[test]
+    fn subsequence_match_is_case_insensitive() {
+        assert!(subsequence_score("ALI", "alice").is_some());
+    }
+
+    #[test]
+    fn consecutive_run_outscores_scattered_match() {
+        let consecutive = subsequence_score("ali", "alice").unwrap();
+        let scattered = subsequence_score("ale", "alice").unwrap();
+        assert!(consecutive > scattered);
+    }
+
+    #[test]
+    fn shorter_candidate_outscores_longer_for_same_match() {
+        let short = subsequence_score("ali", "ali").unwrap();
+        let long = subsequence_score("ali", "alice in wonderland").unwrap();
+        assert!(short > long);
+    }
+
+    #[test]
+    fn word_boundary_match_outscores_mid_word_match() {
+        let boundary = subsequence_score("bob", "Dr Bob Smith").unwrap();
+        let mid_word = subsequence_score("bob", "Abobby").unwrap();
+        assert!(boundary > mid_word);
+    }
+}
@@ -0,0 +1,48 @@
+use std::error::Error;
+use std::fmt;
+
+/// Error type returned by the typed gewe API wrappers.
+///
+/// Raw wrappers (e.g. [`crate::api::fetch_contacts_list`]) keep returning
+/// `Box<dyn Error>` so existing callers are unaffected; typed wrappers
+/// (e.g. [`crate::api::get_brief_list_typed`]) return `GeweError` so callers
+/// can match on the failure mode instead of re-parsing a `Value`.
+#[derive(Debug)]
+pub enum GeweError {
+    /// The underlying HTTP call (via [`crate::util::gewe_post_json`]) failed.
+    ///
+    /// Holds the same `Box<dyn Error>` that [`crate::util::gewe_post_json`]
+    /// returns (no `Send + Sync` bound) since that's what every raw wrapper
+    /// in this crate already propagates.
+    Transport(Box<dyn Error>),
+    /// The response body could not be decoded into the expected shape.
+    Decode(serde_json::Error),
+    /// The gewe service answered with a non-success `ret` code.
+    Api { ret: i32, msg: String },
+}
+
+impl fmt::Display for GeweError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeweError::Transport(err) => write!(f, "gewe request failed: {err}"),
+            GeweError::Decode(err) => write!(f, "gewe response decode failed: {err}"),
+            GeweError::Api { ret, msg } => write!(f, "gewe api error (ret={ret}): {msg}"),
+        }
+    }
+}
+
+impl Error for GeweError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GeweError::Transport(err) => Some(err.as_ref()),
+            GeweError::Decode(err) => Some(err),
+            GeweError::Api { .. } => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for GeweError {
+    fn from(err: serde_json::Error) -> Self {
+        GeweError::Decode(err)
+    }
+}
@@ -0,0 +1,216 @@
+use std::sync::Mutex;
+
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use crate::relationship::Scene;
+
+/// A decoded gewe callback relevant to contacts.
+///
+/// The gewe service pushes these asynchronously to whatever webhook URL the
+/// host app registered; [`decode`] turns the raw JSON body into one of these
+/// variants, and [`EventBus`] fans them out to whoever is listening.
+#[derive(Debug, Clone)]
+pub enum ContactEvent {
+    /// Someone asked to add the authenticated account as a friend.
+    ///
+    /// Carries exactly the fields [`crate::api::accept_friend`] needs, so a
+    /// handler can answer the request without re-parsing anything.
+    FriendRequestReceived {
+        v3: String,
+        v4: String,
+        scene: Scene,
+        content: String,
+    },
+    /// A contact removed the authenticated account, or was removed locally
+    /// on another device.
+    ContactDeleted { wxid: String },
+    /// A contact's remark was changed on another device.
+    RemarkChanged { wxid: String, remark: String },
+}
+
+/// Decodes a raw gewe callback body into a [`ContactEvent`].
+///
+/// Returns `None` for callback types this module doesn't model (e.g.
+/// message callbacks), so callers can route those elsewhere.
+pub fn decode(raw: &Value) -> Option<ContactEvent> {
+    let type_name = raw.get("typeName")?.as_str()?;
+    let data = raw.get("data")?;
+
+    match type_name {
+        "friend_request" => {
+            let v3 = data.get("v3")?.as_str()?.to_string();
+            let v4 = data.get("v4")?.as_str()?.to_string();
+            let scene = Scene::from(data.get("scene").and_then(Value::as_i64).unwrap_or_default() as i32);
+            let content = data
+                .get("content")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            Some(ContactEvent::FriendRequestReceived {
+                v3,
+                v4,
+                scene,
+                content,
+            })
+        }
+        "contact_deleted" => {
+            let wxid = data.get("wxid")?.as_str()?.to_string();
+            Some(ContactEvent::ContactDeleted { wxid })
+        }
+        "remark_changed" => {
+            let wxid = data.get("wxid")?.as_str()?.to_string();
+            let remark = data.get("remark")?.as_str()?.to_string();
+            Some(ContactEvent::RemarkChanged { wxid, remark })
+        }
+        _ => None,
+    }
+}
+
+/// Handles a single decoded [`ContactEvent`].
+///
+/// Register one with [`EventBus::subscribe`] for a callback style instead of
+/// draining the bus's receiver directly.
+pub trait ContactEventHandler: Send + Sync {
+    fn handle(&self, event: ContactEvent);
+}
+
+/// An `mpsc`-backed fan-out point for decoded contact events.
+///
+/// A host web framework feeds raw webhook bodies into
+/// [`dispatch_raw`](Self::dispatch_raw). Every dispatched event goes to both
+/// any [`ContactEventHandler`]s registered via [`subscribe`](Self::subscribe)
+/// and the paired receiver, so callers can pick whichever style — registered
+/// handlers or draining the channel — fits the host framework.
+pub struct EventBus {
+    sender: mpsc::UnboundedSender<ContactEvent>,
+    handlers: Mutex<Vec<Box<dyn ContactEventHandler>>>,
+}
+
+impl EventBus {
+    /// Creates a bus and its receiving half.
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<ContactEvent>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        (
+            Self {
+                sender,
+                handlers: Mutex::new(Vec::new()),
+            },
+            receiver,
+        )
+    }
+
+    /// Registers a handler that runs synchronously for every event this bus
+    /// dispatches, in addition to it being sent on the channel.
+    pub fn subscribe(&self, handler: impl ContactEventHandler + 'static) {
+        self.handlers.lock().unwrap().push(Box::new(handler));
+    }
+
+    /// Decodes `raw` and forwards it to subscribers.
+    ///
+    /// Returns `true` if the body decoded into a known [`ContactEvent`] and
+    /// was forwarded, `false` if it was unrecognized or no receiver remains.
+    pub fn dispatch_raw(&self, raw: &Value) -> bool {
+        match decode(raw) {
+            Some(event) => self.dispatch(event),
+            None => false,
+        }
+    }
+
+    /// Forwards an already-decoded event to registered handlers and the
+    /// channel, e.g. for tests or replays.
+    pub fn dispatch(&self, event: ContactEvent) -> bool {
+        for handler in self.handlers.lock().unwrap().iter() {
+            handler.handle(event.clone());
+        }
+        self.sender.send(event).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn decodes_friend_request() {
+        let raw = json!({
+            "typeName": "friend_request",
+            "data": {"v3": "v3tok", "v4": "v4tok", "scene": 30, "content": "hi"},
+        });
+        match decode(&raw) {
+            Some(ContactEvent::FriendRequestReceived { v3, v4, scene, content }) => {
+                assert_eq!(v3, "v3tok");
+                assert_eq!(v4, "v4tok");
+                assert_eq!(scene, Scene::QrCode);
+                assert_eq!(content, "hi");
+            }
+            other => panic!("expected FriendRequestReceived, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decodes_friend_request_with_unrecognized_scene() {
+        let raw = json!({
+            "typeName": "friend_request",
+            "data": {"v3": "v3tok", "v4": "v4tok", "scene": 999, "content": ""},
+        });
+        match decode(&raw) {
+            Some(ContactEvent::FriendRequestReceived { scene, .. }) => {
+                assert_eq!(scene, Scene::Other(999));
+            }
+            other => panic!("expected FriendRequestReceived, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decodes_contact_deleted_and_remark_changed() {
+        let deleted = json!({"typeName": "contact_deleted", "data": {"wxid": "wxid_1"}});
+        assert!(matches!(
+            decode(&deleted),
+            Some(ContactEvent::ContactDeleted { wxid }) if wxid == "wxid_1"
+        ));
+
+        let remark = json!({"typeName": "remark_changed", "data": {"wxid": "wxid_1", "remark": "bob"}});
+        assert!(matches!(
+            decode(&remark),
+            Some(ContactEvent::RemarkChanged { wxid, remark }) if wxid == "wxid_1" && remark == "bob"
+        ));
+    }
+
+    #[test]
+    fn unknown_type_name_decodes_to_none() {
+        let raw = json!({"typeName": "some_message_callback", "data": {}});
+        assert!(decode(&raw).is_none());
+    }
+
+    struct RecordingHandler {
+        seen: std::sync::Mutex<Vec<ContactEvent>>,
+    }
+
+    impl ContactEventHandler for std::sync::Arc<RecordingHandler> {
+        fn handle(&self, event: ContactEvent) {
+            self.seen.lock().unwrap().push(event);
+        }
+    }
+
+    #[test]
+    fn subscribed_handler_receives_dispatched_events() {
+        let (bus, mut receiver) = EventBus::new();
+        let handler = std::sync::Arc::new(RecordingHandler {
+            seen: std::sync::Mutex::new(Vec::new()),
+        });
+        bus.subscribe(handler.clone());
+
+        let event = ContactEvent::ContactDeleted {
+            wxid: "wxid_1".to_string(),
+        };
+        assert!(bus.dispatch(event));
+
+        assert_eq!(handler.seen.lock().unwrap().len(), 1);
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(ContactEvent::ContactDeleted { wxid }) if wxid == "wxid_1"
+        ));
+    }
+}
@@ -0,0 +1,105 @@
+/// Where a friend request originated, mirrored from gewe's numeric `scene`
+/// values (the same codes `/contacts/search` accepts and
+/// [`crate::contact::SearchResult::scene`] reports back).
+///
+/// Gewe's scene codes aren't limited to the ones named below, so unrecognized
+/// codes land in [`Scene::Other`] instead of being rejected — a friend
+/// request from an unfamiliar scene should still produce an event, not
+/// vanish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scene {
+    /// Added by scanning a QR code.
+    QrCode,
+    /// Added via a synced phone contact.
+    PhoneContact,
+    /// Added by searching a WeChat ID.
+    WeChatId,
+    /// Added from a shared group chat.
+    GroupChat,
+    /// Added via a shared contact card.
+    Card,
+    /// A scene code this crate doesn't have a name for yet.
+    Other(i32),
+}
+
+impl Scene {
+    /// The gewe wire code for this scene.
+    pub fn code(self) -> i32 {
+        match self {
+            Scene::QrCode => 30,
+            Scene::PhoneContact => 13,
+            Scene::WeChatId => 15,
+            Scene::GroupChat => 14,
+            Scene::Card => 17,
+            Scene::Other(code) => code,
+        }
+    }
+}
+
+impl From<i32> for Scene {
+    fn from(value: i32) -> Self {
+        match value {
+            30 => Scene::QrCode,
+            13 => Scene::PhoneContact,
+            15 => Scene::WeChatId,
+            14 => Scene::GroupChat,
+            17 => Scene::Card,
+            other => Scene::Other(other),
+        }
+    }
+}
+
+/// Whether `/contacts/search` should add the contact directly or merely
+/// submit a request pending the other side's verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum AddOption {
+    /// Add without requiring verification from the other side.
+    Add = 2,
+    /// Submit a request that the other side must verify/accept.
+    Verify = 3,
+}
+
+/// The relationship between the authenticated account and a contact.
+///
+/// Drives the add→pending→accepted path in place of magic `ret`/option
+/// integers: [`crate::api::add_friend`] and [`crate::api::accept_friend`]
+/// move a contact through these states, and
+/// [`crate::api::set_friend_only_chat_typed`] /
+/// [`crate::api::delete_friend_typed`] fold their results back into it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelationshipState {
+    /// No relationship exists yet.
+    Stranger,
+    /// A request was sent and is awaiting the other side's verification.
+    RequestSent,
+    /// A request was received from the other side and is awaiting our verification.
+    RequestReceived,
+    /// Both sides are confirmed friends.
+    Friend,
+    /// Friends, but restricted to chat (no moments/feed visibility).
+    ChatOnly,
+    /// The contact has been deleted.
+    Deleted,
+}
+
+/// An incoming or outgoing friend request, carrying the opaque `v3`/`v4`
+/// tokens gewe needs to add or verify a contact.
+#[derive(Debug, Clone)]
+pub struct FriendRequest {
+    pub v3: String,
+    pub v4: String,
+    pub scene: Scene,
+    pub content: String,
+}
+
+impl FriendRequest {
+    pub fn new(v3: impl Into<String>, v4: impl Into<String>, scene: Scene, content: impl Into<String>) -> Self {
+        Self {
+            v3: v3.into(),
+            v4: v4.into(),
+            scene,
+            content: content.into(),
+        }
+    }
+}